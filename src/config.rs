@@ -0,0 +1,84 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Project-finder configuration, loaded from a `project-finder.toml` file.
+/// Any section that is missing from the file falls back to its default.
+#[derive(Debug, Deserialize, Default)]
+pub struct Config {
+    #[serde(default)]
+    pub kinds: KindsConfig,
+    #[serde(default)]
+    pub custom_kinds: Vec<CustomKind>,
+    #[serde(default)]
+    pub display: DisplayConfig,
+}
+
+/// Toggles for the built-in project kinds, all on by default.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct KindsConfig {
+    pub nodejs: bool,
+    pub rust: bool,
+    pub go: bool,
+    pub python: bool,
+    pub dotnet: bool,
+}
+
+impl Default for KindsConfig {
+    fn default() -> Self {
+        KindsConfig {
+            nodejs: true,
+            rust: true,
+            go: true,
+            python: true,
+            dotnet: true,
+        }
+    }
+}
+
+/// A user-defined project kind, matched the same way as the built-in ones:
+/// present if any listed file, folder, or extension is found.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CustomKind {
+    pub name: String,
+    #[serde(default)]
+    pub files: Vec<String>,
+    #[serde(default)]
+    pub folders: Vec<String>,
+    #[serde(default)]
+    pub extensions: Vec<String>,
+}
+
+/// Rendering preferences for the `Display` impl.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    pub color: bool,
+    pub show_git_sync: bool,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        DisplayConfig {
+            color: true,
+            show_git_sync: true,
+        }
+    }
+}
+
+impl Config {
+    /// Load configuration from `path`, or from the default location if
+    /// `path` is `None`. Falls back to `Config::default()` when no file is
+    /// found or it fails to parse, rather than treating that as fatal.
+    pub fn load(path: Option<&Path>) -> Config {
+        let path = path.map(PathBuf::from).or_else(default_config_path);
+        path.and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    dirs_next::config_dir().map(|config_dir| config_dir.join("project-finder.toml"))
+}
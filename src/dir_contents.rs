@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A single `fs::read_dir` pass over a directory, partitioned so that
+/// membership checks (`has_file`, `has_folder`, `has_extension`) are O(1)
+/// instead of a fresh syscall per candidate.
+pub struct DirContents {
+    files: HashSet<String>,
+    folders: HashSet<String>,
+    extensions: HashSet<String>,
+    entries: Vec<PathBuf>,
+}
+
+impl DirContents {
+    pub fn from_path(dir: &Path) -> io::Result<Self> {
+        let mut files = HashSet::new();
+        let mut folders = HashSet::new();
+        let mut extensions = HashSet::new();
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            // Follow symlinks here (unlike `DirEntry::file_type`) so a
+            // symlinked `node_modules`/`target` is still recognized as a
+            // marker folder, matching the `is_dir`/`is_file` helpers this
+            // replaced.
+            if path.is_dir() {
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    folders.insert(name.to_string());
+                }
+            } else if path.is_file() {
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    files.insert(name.to_string());
+                }
+                if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+                    extensions.insert(ext.to_string());
+                }
+            }
+            entries.push(path);
+        }
+
+        Ok(DirContents {
+            files,
+            folders,
+            extensions,
+            entries,
+        })
+    }
+
+    pub fn has_file(&self, name: &str) -> bool {
+        self.files.contains(name)
+    }
+
+    pub fn has_folder(&self, name: &str) -> bool {
+        self.folders.contains(name)
+    }
+
+    pub fn has_extension(&self, extension: &str) -> bool {
+        self.extensions.contains(extension)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &PathBuf> {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+impl DirContents {
+    /// Build a `DirContents` from in-memory sets, bypassing `fs::read_dir`,
+    /// so callers like `project_kind`'s tests can exercise matching logic
+    /// without touching the filesystem.
+    pub(crate) fn from_parts(files: &[&str], folders: &[&str], extensions: &[&str]) -> Self {
+        DirContents {
+            files: files.iter().map(|s| s.to_string()).collect(),
+            folders: folders.iter().map(|s| s.to_string()).collect(),
+            extensions: extensions.iter().map(|s| s.to_string()).collect(),
+            entries: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use git2::{BranchType, Repository, StatusOptions};
+use serde::Serialize;
+
+/// Git status of a discovered project, derived from an on-disk repository.
+#[derive(Serialize)]
+pub struct Git {
+    pub clean: bool,
+    pub nosync: NoSync,
+}
+
+/// Sync state of the current branch relative to its upstream.
+#[derive(PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoSync {
+    /// Local branch matches its upstream.
+    Synced,
+    /// Local branch is ahead and/or behind its upstream.
+    Unsynced,
+    /// The current branch has no upstream configured (or HEAD is detached
+    /// or unborn), so there is nothing to compare against.
+    NoUpstream,
+}
+
+/// Inspect the repository rooted at `path` and report worktree cleanliness
+/// and upstream sync state. Returns `None` if `path` is not a Git repository.
+pub fn inspect(path: &std::path::Path) -> Option<Git> {
+    let repo = Repository::open(path).ok()?;
+    let clean = is_clean(&repo);
+    let nosync = sync_state(&repo);
+    Some(Git { clean, nosync })
+}
+
+fn is_clean(repo: &Repository) -> bool {
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).include_ignored(false);
+    match repo.statuses(Some(&mut options)) {
+        Ok(statuses) => statuses.is_empty(),
+        // If we can't read status (e.g. bare repo), assume clean rather than panic.
+        Err(_) => true,
+    }
+}
+
+fn sync_state(repo: &Repository) -> NoSync {
+    let head = match repo.head() {
+        Ok(head) => head,
+        // Unborn branch: no commits yet, nothing to sync.
+        Err(_) => return NoSync::NoUpstream,
+    };
+    let branch_name = match head.shorthand() {
+        Some(name) if head.is_branch() => name,
+        // Detached HEAD: no upstream to compare against.
+        _ => return NoSync::NoUpstream,
+    };
+    let branch = match repo.find_branch(branch_name, BranchType::Local) {
+        Ok(branch) => branch,
+        Err(_) => return NoSync::NoUpstream,
+    };
+    let upstream = match branch.upstream() {
+        Ok(upstream) => upstream,
+        Err(_) => return NoSync::NoUpstream,
+    };
+    let local_oid = match branch.get().target() {
+        Some(oid) => oid,
+        None => return NoSync::NoUpstream,
+    };
+    let upstream_oid = match upstream.get().target() {
+        Some(oid) => oid,
+        None => return NoSync::NoUpstream,
+    };
+    match repo.graph_ahead_behind(local_oid, upstream_oid) {
+        Ok((ahead, behind)) if ahead == 0 && behind == 0 => NoSync::Synced,
+        Ok(_) => NoSync::Unsynced,
+        Err(_) => NoSync::NoUpstream,
+    }
+}
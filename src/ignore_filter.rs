@@ -0,0 +1,95 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::path::Path;
+
+/// Accumulated `.gitignore`/`.ignore` rules gathered while descending a
+/// directory tree, most specific (deepest) rules last.
+#[derive(Clone, Default)]
+pub struct IgnoreStack {
+    layers: Vec<Gitignore>,
+}
+
+impl IgnoreStack {
+    pub fn new() -> Self {
+        IgnoreStack { layers: Vec::new() }
+    }
+
+    /// Return a new stack with `dir`'s own `.gitignore`/`.ignore` rules
+    /// layered on top of the parent rules already in this stack.
+    pub fn descend(&self, dir: &Path) -> Self {
+        let mut builder = GitignoreBuilder::new(dir);
+        builder.add(dir.join(".gitignore"));
+        builder.add(dir.join(".ignore"));
+        let gitignore = builder.build().unwrap_or_else(|_| Gitignore::empty());
+
+        let mut layers = self.layers.clone();
+        layers.push(gitignore);
+        IgnoreStack { layers }
+    }
+
+    /// Whether `path` should be skipped, checking layers from the root down
+    /// so a deeper `!negation` can override a shallower ignore rule.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for gitignore in &self.layers {
+            match gitignore.matched(path, is_dir) {
+                Match::Ignore(_) => ignored = true,
+                Match::Whitelist(_) => ignored = false,
+                Match::None => {}
+            }
+        }
+        ignored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// A fresh scratch directory under the OS temp dir, torn down on drop.
+    struct ScratchDir(std::path::PathBuf);
+
+    impl ScratchDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "project-finder-ignore-filter-test-{}-{}",
+                std::process::id(),
+                name
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            ScratchDir(dir)
+        }
+    }
+
+    impl Drop for ScratchDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn a_child_negation_overrides_a_parent_ignore() {
+        let root = ScratchDir::new("negation");
+        fs::write(root.0.join(".gitignore"), "*.log\n").unwrap();
+        let child = root.0.join("keep");
+        fs::create_dir_all(&child).unwrap();
+        fs::write(child.join(".gitignore"), "!important.log\n").unwrap();
+
+        let stack = IgnoreStack::new().descend(&root.0).descend(&child);
+
+        assert!(stack.is_ignored(&child.join("debug.log"), false));
+        assert!(!stack.is_ignored(&child.join("important.log"), false));
+    }
+
+    #[test]
+    fn a_path_with_no_matching_rule_is_not_ignored() {
+        let root = ScratchDir::new("unmatched");
+        fs::write(root.0.join(".gitignore"), "*.log\n").unwrap();
+
+        let stack = IgnoreStack::new().descend(&root.0);
+
+        assert!(!stack.is_ignored(&root.0.join("main.rs"), false));
+    }
+}
@@ -1,23 +1,28 @@
+mod config;
+mod dir_contents;
+mod git;
+mod ignore_filter;
+mod project_kind;
+
 use clap::{App, Arg};
+use config::Config;
+use dir_contents::DirContents;
+use git::{Git, NoSync};
+use ignore_filter::IgnoreStack;
+use project_kind::ProjectKind;
+use serde::Serialize;
 use std::fmt;
-use std::fs::{self, DirEntry};
 use std::io;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use colored::*;
 
-pub struct Git {
-    clean: bool,
-    nosync: bool,
-}
-
-pub enum ProjectKind {
-    NodeJS { installed: bool, lockfile: bool },
-    Rust { installed: bool },
-}
-
+#[derive(Serialize)]
 pub struct Project {
+    path: String,
     git: Option<Git>,
     kind: Option<ProjectKind>,
+    #[serde(skip)]
+    show_git_sync: bool,
 }
 
 impl Project {
@@ -28,14 +33,27 @@ impl Project {
 
 impl fmt::Display for Project {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let &Some(Git { clean, nosync }) = &self.git {
-            write!(
-                f,
-                "  found {} with {} worktree and {}",
-                "Git".bold(),
-                if clean { "clean" } else { "dirty" },
-                if nosync { "need sync" } else { "synced" }
-            )?;
+        if let Some(Git { clean, nosync }) = &self.git {
+            if self.show_git_sync {
+                write!(
+                    f,
+                    "  found {} with {} worktree and {}",
+                    "Git".bold(),
+                    if *clean { "clean" } else { "dirty" },
+                    match nosync {
+                        NoSync::Synced => "synced",
+                        NoSync::Unsynced => "need sync",
+                        NoSync::NoUpstream => "no upstream",
+                    }
+                )?;
+            } else {
+                write!(
+                    f,
+                    "  found {} with {} worktree",
+                    "Git".bold(),
+                    if *clean { "clean" } else { "dirty" },
+                )?;
+            }
         } else {
             write!(f, "no {} found", "Git".bold(),)?;
         }
@@ -65,72 +83,80 @@ impl fmt::Display for Project {
                         "uninitialized"
                     },
                 )?,
+                ProjectKind::Go => write!(f, "  found {}", "Go".bold())?,
+                ProjectKind::Python => write!(f, "  found {}", "Python".bold())?,
+                ProjectKind::DotNet => write!(f, "  found {}", ".NET".bold())?,
+                ProjectKind::Custom { name } => write!(f, "  found {}", name.bold())?,
             }
         }
         Ok(())
     }
 }
 
-fn is_file(path_buf: &mut PathBuf, file_name: &str) -> bool {
-    path_buf.push(file_name);
-    let result = path_buf.is_file();
-    path_buf.pop();
-    result
+fn examine(directory: &Path, contents: &DirContents, config: &Config) -> Project {
+    // Open the repository (if any) and inspect its real status.
+    let git = git::inspect(directory);
+    let kind = project_kind::detect(contents, config);
+    Project {
+        path: directory.display().to_string(),
+        git,
+        kind,
+        show_git_sync: config.display.show_git_sync,
+    }
 }
 
-fn is_dir(path_buf: &mut PathBuf, file_name: &str) -> bool {
-    path_buf.push(file_name);
-    let result = path_buf.is_dir();
-    path_buf.pop();
-    result
+/// How discovered projects are reported.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Colorized, human-readable text (the default).
+    Text,
+    /// A single JSON array emitted once the whole scan has finished.
+    Json,
+    /// One JSON object per line, streamed as each project is found.
+    Ndjson,
 }
 
-fn examine(directory: &Path) -> Project {
-    let mut path_buf = directory.to_path_buf();
-    // Check if the folder has a .git folder.
-    path_buf.push(".git");
-    let git = if path_buf.is_dir() {
-        Some(Git {
-            clean: true,
-            nosync: true,
-        })
-    } else {
-        None
-    };
-    path_buf.pop();
-    let has_package_json = is_file(&mut path_buf, "package.json");
-    let has_lockfile = is_file(&mut path_buf, "package-lock.json") || is_file(&mut path_buf, "yarn.lock");
-    let has_node_modules = is_dir(&mut path_buf, "node_modules");
-    let kind = if has_package_json || has_lockfile || has_node_modules {
-        Some(ProjectKind::NodeJS {
-            installed: has_node_modules,
-            lockfile: has_lockfile,
-        })
+fn visit_dirs(
+    dir: &Path,
+    ignore_stack: &IgnoreStack,
+    respect_ignore: bool,
+    config: &Config,
+    format: OutputFormat,
+    found: &mut Vec<Project>,
+) -> io::Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let contents = DirContents::from_path(dir)?;
+    let project = examine(dir, &contents, config);
+    if project.is_project() {
+        match format {
+            OutputFormat::Text => {
+                let dir_path = project.path.clone().green();
+                println!("[{}]", dir_path);
+                println!("{}", project);
+            }
+            OutputFormat::Ndjson => {
+                if let Ok(line) = serde_json::to_string(&project) {
+                    println!("{}", line);
+                }
+            }
+            OutputFormat::Json => found.push(project),
+        }
     } else {
-        None
-    };
-    path_buf.pop();
-    // Ending
-    Project { git, kind }
-}
-
-fn visit_dirs(dir: &Path, cb: &dyn Fn(&DirEntry)) -> io::Result<()> {
-    if dir.is_dir() {
-        let project = examine(dir);
-        if project.is_project() {
-            let dir_path = dir.display().to_string().green();
-            println!("[{}]", dir_path);
-            println!("{}", project);
+        let ignore_stack = if respect_ignore {
+            ignore_stack.descend(dir)
         } else {
-            for entry in fs::read_dir(dir)? {
-                let entry = entry?;
-                let path = entry.path();
-                if path.is_dir() {
-                    visit_dirs(&path, cb)?;
-                } else {
-                    cb(&entry);
-                }
+            ignore_stack.clone()
+        };
+        for path in contents.entries() {
+            if !path.is_dir() {
+                continue;
+            }
+            if respect_ignore && ignore_stack.is_ignored(path, true) {
+                continue;
             }
+            visit_dirs(path, &ignore_stack, respect_ignore, config, format, found)?;
         }
     }
     Ok(())
@@ -159,12 +185,51 @@ fn main() -> Result<(), AppError> {
                 .required(true)
                 .index(1),
         )
+        .arg(
+            Arg::new("no-ignore")
+                .long("no-ignore")
+                .about("Scan every directory, ignoring .gitignore/.ignore files."),
+        )
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .takes_value(true)
+                .about("Path to a project-finder.toml config file (defaults to ~/.config/project-finder.toml)."),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .takes_value(true)
+                .possible_values(&["text", "json", "ndjson"])
+                .default_value("text")
+                .about("Output format: colorized text, a single JSON array, or newline-delimited JSON."),
+        )
         .get_matches();
     let input_directory = matches
         .value_of("INPUT")
         .ok_or(AppError::ArgNotFoundError)?;
-    visit_dirs(Path::new(input_directory), &|_| {
-        // println!("{:?}", entry.file_name());
-    })?;
+    let respect_ignore = !matches.is_present("no-ignore");
+    let config = Config::load(matches.value_of("config").map(Path::new));
+    if !config.display.color {
+        colored::control::set_override(false);
+    }
+    let format = match matches.value_of("format") {
+        Some("json") => OutputFormat::Json,
+        Some("ndjson") => OutputFormat::Ndjson,
+        _ => OutputFormat::Text,
+    };
+    let mut found = Vec::new();
+    visit_dirs(
+        Path::new(input_directory),
+        &IgnoreStack::new(),
+        respect_ignore,
+        &config,
+        format,
+        &mut found,
+    )?;
+    if format == OutputFormat::Json {
+        let json = serde_json::to_string(&found).map_err(|error| AppError::IOError(error.into()))?;
+        println!("{}", json);
+    }
     Ok(())
 }
@@ -0,0 +1,161 @@
+use crate::config::Config;
+use crate::dir_contents::DirContents;
+use serde::Serialize;
+
+/// A declarative detection rule: a directory satisfies a `ScanDir` if it
+/// contains any of the listed files, folders, or extensions.
+pub struct ScanDir<'a> {
+    pub files: &'a [&'a str],
+    pub folders: &'a [&'a str],
+    pub extensions: &'a [&'a str],
+}
+
+impl<'a> ScanDir<'a> {
+    const fn new() -> Self {
+        ScanDir {
+            files: &[],
+            folders: &[],
+            extensions: &[],
+        }
+    }
+
+    const fn files(self, files: &'a [&'a str]) -> Self {
+        ScanDir { files, ..self }
+    }
+
+    const fn folders(self, folders: &'a [&'a str]) -> Self {
+        ScanDir { folders, ..self }
+    }
+
+    const fn extensions(self, extensions: &'a [&'a str]) -> Self {
+        ScanDir { extensions, ..self }
+    }
+
+    fn is_match(&self, contents: &DirContents) -> bool {
+        self.files.iter().any(|file| contents.has_file(file))
+            || self.folders.iter().any(|folder| contents.has_folder(folder))
+            || self
+                .extensions
+                .iter()
+                .any(|extension| contents.has_extension(extension))
+    }
+}
+
+/// A project kind matches a directory if any of its criteria are satisfied.
+fn matches_any(criteria: &[ScanDir], contents: &DirContents) -> bool {
+    criteria.iter().any(|scan_dir| scan_dir.is_match(contents))
+}
+
+const NODEJS_CRITERIA: &[ScanDir] = &[ScanDir::new()
+    .files(&["package.json", "package-lock.json", "yarn.lock"])
+    .folders(&["node_modules"])];
+
+const RUST_CRITERIA: &[ScanDir] = &[ScanDir::new().files(&["Cargo.toml"]).folders(&["target"])];
+
+const GO_CRITERIA: &[ScanDir] = &[ScanDir::new().files(&["go.mod"])];
+
+const PYTHON_CRITERIA: &[ScanDir] = &[ScanDir::new()
+    .files(&["pyproject.toml", "requirements.txt", "setup.py"])
+    .extensions(&["py"])];
+
+const DOTNET_CRITERIA: &[ScanDir] = &[ScanDir::new().extensions(&["csproj", "sln", "fsproj"])];
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProjectKind {
+    #[serde(rename = "nodejs")]
+    NodeJS { installed: bool, lockfile: bool },
+    Rust { installed: bool },
+    Go,
+    Python,
+    DotNet,
+    Custom { name: String },
+}
+
+/// Detect the project kind of a directory from its already-read contents,
+/// checking each kind's criteria in turn. Built-in kinds can be switched off
+/// via `config.kinds`, and `config.custom_kinds` are checked last.
+pub fn detect(contents: &DirContents, config: &Config) -> Option<ProjectKind> {
+    if config.kinds.nodejs && matches_any(NODEJS_CRITERIA, contents) {
+        return Some(ProjectKind::NodeJS {
+            installed: contents.has_folder("node_modules"),
+            lockfile: contents.has_file("package-lock.json") || contents.has_file("yarn.lock"),
+        });
+    }
+    if config.kinds.rust && matches_any(RUST_CRITERIA, contents) {
+        return Some(ProjectKind::Rust {
+            installed: contents.has_folder("target"),
+        });
+    }
+    if config.kinds.go && matches_any(GO_CRITERIA, contents) {
+        return Some(ProjectKind::Go);
+    }
+    if config.kinds.python && matches_any(PYTHON_CRITERIA, contents) {
+        return Some(ProjectKind::Python);
+    }
+    if config.kinds.dotnet && matches_any(DOTNET_CRITERIA, contents) {
+        return Some(ProjectKind::DotNet);
+    }
+    for custom in &config.custom_kinds {
+        if custom_kind_matches(custom, contents) {
+            return Some(ProjectKind::Custom {
+                name: custom.name.clone(),
+            });
+        }
+    }
+    None
+}
+
+/// Like `ScanDir::is_match`, but for a user-defined kind whose file/folder/
+/// extension lists are owned `String`s loaded from the config file rather
+/// than `&'static str`s known at compile time.
+fn custom_kind_matches(custom: &crate::config::CustomKind, contents: &DirContents) -> bool {
+    custom.files.iter().any(|file| contents.has_file(file))
+        || custom.folders.iter().any(|folder| contents.has_folder(folder))
+        || custom
+            .extensions
+            .iter()
+            .any(|extension| contents.has_extension(extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_dir_matches_on_a_listed_file() {
+        let scan_dir = ScanDir::new().files(&["Cargo.toml"]).folders(&["target"]);
+        let contents = DirContents::from_parts(&["Cargo.toml"], &[], &[]);
+        assert!(scan_dir.is_match(&contents));
+    }
+
+    #[test]
+    fn scan_dir_matches_on_a_listed_folder_without_the_file() {
+        let scan_dir = ScanDir::new().files(&["Cargo.toml"]).folders(&["target"]);
+        let contents = DirContents::from_parts(&[], &["target"], &[]);
+        assert!(scan_dir.is_match(&contents));
+    }
+
+    #[test]
+    fn scan_dir_does_not_match_unrelated_contents() {
+        let scan_dir = ScanDir::new().files(&["Cargo.toml"]).folders(&["target"]);
+        let contents = DirContents::from_parts(&["README.md"], &[], &[]);
+        assert!(!scan_dir.is_match(&contents));
+    }
+
+    #[test]
+    fn matches_any_is_true_if_any_criteria_in_the_list_matches() {
+        let go = ScanDir::new().files(&["go.mod"]);
+        let rust = ScanDir::new().files(&["Cargo.toml"]).folders(&["target"]);
+        let contents = DirContents::from_parts(&["Cargo.toml"], &[], &[]);
+        assert!(matches_any(&[go, rust], &contents));
+    }
+
+    #[test]
+    fn matches_any_is_false_if_no_criteria_matches() {
+        let go = ScanDir::new().files(&["go.mod"]);
+        let rust = ScanDir::new().files(&["Cargo.toml"]).folders(&["target"]);
+        let contents = DirContents::from_parts(&["README.md"], &[], &[]);
+        assert!(!matches_any(&[go, rust], &contents));
+    }
+}